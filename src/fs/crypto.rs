@@ -1,10 +1,30 @@
 /// Module to perform cryptographic file related operations.
 
-use ring::digest::{Context, Digest, SHA256};
-use std::fs::File;
+use ring::digest::{Algorithm, Context, Digest, SHA256, SHA384, SHA512};
+use std::fs::{metadata, File};
 use std::io::{BufReader, Read, Error};
+use std::path::{Path, PathBuf};
 use std::str;
 
+/// Hash algorithms supported by hash_file_with. hash_dir always uses SHA-256.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HashAlgorithm {
+    SHA256,
+    SHA384,
+    SHA512,
+}
+
+impl HashAlgorithm {
+    /// Return the ring digest Algorithm matching this variant.
+    fn algorithm(&self) -> &'static Algorithm {
+        match self {
+            HashAlgorithm::SHA256 => &SHA256,
+            HashAlgorithm::SHA384 => &SHA384,
+            HashAlgorithm::SHA512 => &SHA512,
+        }
+    }
+}
+
 /// Hash file content with SHA-256.
 ///
 /// This way we can check two files have same content.
@@ -17,8 +37,20 @@ use std::str;
 /// # Returns:
 /// File has as a Digest or a Error if any ocurred.
 pub fn hash_file(file_path: &str) -> Result<Digest, Error> {
+    hash_file_with(file_path, HashAlgorithm::SHA256)
+}
+
+/// Hash file content with given algorithm.
+///
+/// # Parameters:
+/// * file_path: Absolute path name as a &str.
+/// * algorithm: HashAlgorithm to use while hashing file content.
+///
+/// # Returns:
+/// File hash as a Digest or a Error if any ocurred.
+pub fn hash_file_with(file_path: &str, algorithm: HashAlgorithm) -> Result<Digest, Error> {
     let mut reader = BufReader::new(File::open(file_path)?);
-    let mut context = Context::new(&SHA256);
+    let mut context = Context::new(algorithm.algorithm());
     let mut buffer = [0; 1024];
 
     loop {
@@ -32,11 +64,80 @@ pub fn hash_file(file_path: &str) -> Result<Digest, Error> {
     Ok(context.finish())
 }
 
+/// Check whether two files hold the same content.
+///
+/// File sizes are compared first, so differing files short-circuit without hashing.
+///
+/// # Parameters:
+/// * file_path_a: Absolute path name to first file as a &str.
+/// * file_path_b: Absolute path name to second file as a &str.
+///
+/// # Returns:
+/// true if both files have the same content, false otherwise, or a Error if any ocurred.
+pub fn files_have_same_content(file_path_a: &str, file_path_b: &str) -> Result<bool, Error> {
+    if metadata(file_path_a)?.len() != metadata(file_path_b)?.len() {
+        return Ok(false);
+    }
+    let hash_a = hash_file(file_path_a)?;
+    let hash_b = hash_file(file_path_b)?;
+    Ok(hash_a.as_ref() == hash_b.as_ref())
+}
+
+/// Hash a whole directory tree into a single SHA-256 digest.
+///
+/// Every file relative path is hashed alongside its content digest, in sorted path order, so two
+/// directory trees produce the same digest if, and only if, they are content-identical. Each
+/// path is prefixed with its byte length and each content digest has SHA-256's fixed size, so
+/// there is no ambiguity about where one entry ends and the next begins.
+///
+/// # Parameters:
+/// * dir_path: Absolute path name to directory as a &str.
+///
+/// # Returns:
+/// Directory hash as a Digest or a Error if any ocurred.
+pub fn hash_dir(dir_path: &str) -> Result<Digest, Error> {
+    let base = Path::new(dir_path);
+    let mut relative_file_paths = Vec::new();
+    collect_file_paths(base, base, &mut relative_file_paths)?;
+    relative_file_paths.sort();
+
+    let mut context = Context::new(&SHA256);
+    for relative_file_path in relative_file_paths {
+        let relative_file_path_str = relative_file_path.to_str()
+            .expect("Directory entry name has non valid unicode characters.");
+        let path_bytes = relative_file_path_str.as_bytes();
+        context.update(&(path_bytes.len() as u64).to_be_bytes());
+        context.update(path_bytes);
+        let file_path = base.join(&relative_file_path);
+        let file_digest = hash_file_with(file_path.to_str()
+            .expect("Directory entry name has non valid unicode characters."), HashAlgorithm::SHA256)?;
+        context.update(file_digest.as_ref());
+    }
+    Ok(context.finish())
+}
+
+/// Recursively collect, relative to base, every file path found under current.
+fn collect_file_paths(base: &Path, current: &Path, relative_file_paths: &mut Vec<PathBuf>) -> Result<(), Error> {
+    for entry in std::fs::read_dir(current)? {
+        let entry = entry?;
+        let entry_path = entry.path();
+        if entry_path.is_dir() {
+            collect_file_paths(base, &entry_path, relative_file_paths)?;
+        } else {
+            let relative_file_path = entry_path.strip_prefix(base)
+                .expect("Error computing relative path inside directory tree.")
+                .to_path_buf();
+            relative_file_paths.push(relative_file_path);
+        }
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use data_encoding::HEXUPPER;
-    use tempfile::NamedTempFile;
+    use tempfile::{NamedTempFile, tempdir};
     use std::io::Write;
 
     #[test]
@@ -56,4 +157,107 @@ mod tests {
             "Recovered hash is not what we were expecting. Expected {} but got {}.",
             expected_hash, recovered_hash);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_hash_file_with_sha512() {
+        let mut file = NamedTempFile::new()
+            .expect("Error creating temporal file.");
+        file.write_all(b"foobar")
+            .expect("Error writing content to temporal file for hashing.");
+        let file_path = file.path();
+        let sha256_hash = hash_file_with(file_path.as_os_str().to_str()
+            .expect("Error getting temporal file path."), HashAlgorithm::SHA256)
+            .expect("Error getting temporal file SHA-256 hash.");
+        let sha512_hash = hash_file_with(file_path.as_os_str().to_str()
+            .expect("Error getting temporal file path."), HashAlgorithm::SHA512)
+            .expect("Error getting temporal file SHA-512 hash.");
+        assert_ne!(sha256_hash.as_ref(), sha512_hash.as_ref(),
+                   "SHA-256 and SHA-512 hashes for same content should not match.");
+    }
+
+    #[test]
+    fn test_files_have_same_content() {
+        let mut file_a = NamedTempFile::new()
+            .expect("Error creating temporal file a.");
+        let mut file_b = NamedTempFile::new()
+            .expect("Error creating temporal file b.");
+        file_a.write_all(b"same content")
+            .expect("Error writing content to temporal file a.");
+        file_b.write_all(b"same content")
+            .expect("Error writing content to temporal file b.");
+        let same = files_have_same_content(
+            file_a.path().to_str().expect("Error getting temporal file a path."),
+            file_b.path().to_str().expect("Error getting temporal file b path."))
+            .expect("Error comparing file contents.");
+        assert!(same, "Files with same content were reported as different.");
+    }
+
+    #[test]
+    fn test_files_have_different_content() {
+        let mut file_a = NamedTempFile::new()
+            .expect("Error creating temporal file a.");
+        let mut file_b = NamedTempFile::new()
+            .expect("Error creating temporal file b.");
+        file_a.write_all(b"content a")
+            .expect("Error writing content to temporal file a.");
+        file_b.write_all(b"content b, longer")
+            .expect("Error writing content to temporal file b.");
+        let same = files_have_same_content(
+            file_a.path().to_str().expect("Error getting temporal file a path."),
+            file_b.path().to_str().expect("Error getting temporal file b path."))
+            .expect("Error comparing file contents.");
+        assert!(!same, "Files with different content were reported as same.");
+    }
+
+    #[test]
+    fn test_hash_dir_matches_for_identical_trees() {
+        let dir_a = tempdir().expect("Error creating temporal directory a.");
+        let dir_b = tempdir().expect("Error creating temporal directory b.");
+        std::fs::create_dir_all(dir_a.path().join("nested"))
+            .expect("Error creating nested directory a.");
+        std::fs::create_dir_all(dir_b.path().join("nested"))
+            .expect("Error creating nested directory b.");
+        std::fs::write(dir_a.path().join("nested/file.txt"), b"content")
+            .expect("Error writing file a.");
+        std::fs::write(dir_b.path().join("nested/file.txt"), b"content")
+            .expect("Error writing file b.");
+        let hash_a = hash_dir(dir_a.path().to_str().expect("Error getting directory a path."))
+            .expect("Error hashing directory a.");
+        let hash_b = hash_dir(dir_b.path().to_str().expect("Error getting directory b path."))
+            .expect("Error hashing directory b.");
+        assert_eq!(hash_a.as_ref(), hash_b.as_ref(),
+                   "Identical directory trees produced different hashes.");
+    }
+
+    #[test]
+    fn test_hash_dir_differs_for_different_trees() {
+        let dir_a = tempdir().expect("Error creating temporal directory a.");
+        let dir_b = tempdir().expect("Error creating temporal directory b.");
+        std::fs::write(dir_a.path().join("file.txt"), b"content a")
+            .expect("Error writing file a.");
+        std::fs::write(dir_b.path().join("file.txt"), b"content b")
+            .expect("Error writing file b.");
+        let hash_a = hash_dir(dir_a.path().to_str().expect("Error getting directory a path."))
+            .expect("Error hashing directory a.");
+        let hash_b = hash_dir(dir_b.path().to_str().expect("Error getting directory b path."))
+            .expect("Error hashing directory b.");
+        assert_ne!(hash_a.as_ref(), hash_b.as_ref(),
+                   "Different directory trees produced the same hash.");
+    }
+
+    #[test]
+    fn test_hash_dir_does_not_collide_across_path_content_boundary() {
+        let dir_a = tempdir().expect("Error creating temporal directory a.");
+        let dir_b = tempdir().expect("Error creating temporal directory b.");
+        std::fs::write(dir_a.path().join("ab"), b"cd")
+            .expect("Error writing file a.");
+        std::fs::write(dir_b.path().join("abc"), b"d")
+            .expect("Error writing file b.");
+        let hash_a = hash_dir(dir_a.path().to_str().expect("Error getting directory a path."))
+            .expect("Error hashing directory a.");
+        let hash_b = hash_dir(dir_b.path().to_str().expect("Error getting directory b path."))
+            .expect("Error hashing directory b.");
+        assert_ne!(hash_a.as_ref(), hash_b.as_ref(),
+                   "Directory trees with different path/content boundaries produced the same hash.");
+    }
+}
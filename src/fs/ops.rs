@@ -1,8 +1,11 @@
 /// Module to perform filesystem typical operations, like copy or remove files.
 
-use std::fs::{remove_file, copy};
-use std::io;
+use std::fs::{remove_file, copy, create_dir_all, read_dir, read_link, rename, write};
+use std::io::{self, Write};
 use std::path::Path;
+use tempfile::Builder;
+
+use crate::random::bytes::random_bytes;
 
 /// Delete an specific file.
 ///
@@ -73,6 +76,146 @@ pub fn copy_files<T>(files: Vec<T>, destination_folder_path: &str)-> Result<(),
     Ok(())
 }
 
+/// Write contents to destination_path without readers ever observing a partially written file.
+///
+/// Content is first written to a temporal file created in the same directory as
+/// destination_path, flushed and then renamed into place, as rename is an atomic
+/// operation in most filesystems. Missing parent directories of destination_path are created
+/// first. If temporal file and destination_path happen to live in different filesystems rename
+/// fails, and content is copied over instead, losing the atomicity guarantee in that case.
+///
+/// # Parameters:
+/// * destination_path: &str with the absolute path to final file.
+/// * contents: Bytes to write into destination_path.
+///
+/// # Returns:
+/// Returns Ok(()) if sucessful and std::io::Error if not.
+pub fn atomic_write(destination_path: &str, contents: &[u8])-> Result<(), io::Error> {
+    let destination = Path::new(destination_path);
+    let parent = destination.parent().unwrap_or_else(|| Path::new("."));
+    create_dir_all(parent)?;
+    let mut temp_file = Builder::new()
+        .prefix(".atomic_write-")
+        .tempfile_in(parent)?;
+    temp_file.write_all(contents)?;
+    temp_file.flush()?;
+    let temp_path = temp_file.into_temp_path();
+    if rename(&temp_path, destination).is_err() {
+        copy(&temp_path, destination)?;
+        remove_file(&temp_path)?;
+    }
+    Ok(())
+}
+
+/// Fill a file with size bytes of random binary content.
+///
+/// # Parameters:
+/// * file_path: &str with the absolute path to file. Created if missing, overwritten if not.
+/// * size: Desired byte length for generated file content.
+///
+/// # Returns:
+/// Returns Ok(()) if sucessful and std::io::Error if not.
+pub fn write_random_file(file_path: &str, size: usize)-> Result<(), io::Error> {
+    write(file_path, random_bytes(size))
+}
+
+/// Options controlling how copy_dir behaves when it encounters existing entries, symlinks or
+/// a missing source directory.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CopyOptions {
+    /// Overwrite destination entries that already exist.
+    pub overwrite: bool,
+    /// Silently skip destination entries that already exist, instead of erroring.
+    pub skip_existing: bool,
+    /// Recreate symlinks as symlinks instead of copying the content they point to.
+    pub copy_symlinks_as_links: bool,
+    /// Return Ok(0) instead of an io::Error when source does not exist.
+    pub ignore_missing: bool,
+}
+
+/// Recursively copy a directory tree, recreating its structure at destination.
+///
+/// # Parameters:
+/// * source: &str with the absolute path to source directory.
+/// * destination: &str with the absolute path to destination directory. Created if missing.
+/// * options: CopyOptions controlling overwrite/skip/symlink/missing-source behaviour.
+///
+/// # Returns:
+/// Returns the total amount of copied bytes as u64 if successful, or an io::Error if not.
+pub fn copy_dir(source: &str, destination: &str, options: CopyOptions)-> Result<u64, io::Error> {
+    let source_path = Path::new(source);
+    if !source_path.exists() {
+        return if options.ignore_missing {
+            Ok(0)
+        } else {
+            Err(io::Error::new(io::ErrorKind::NotFound,
+                                format!("Source directory {} does not exist.", source)))
+        };
+    }
+    create_dir_all(destination)?;
+    let mut total_bytes_copied = 0u64;
+    for entry in read_dir(source_path)? {
+        let entry = entry?;
+        let entry_path = entry.path();
+        let file_type = entry.file_type()?;
+        let destination_entry_path = Path::new(destination).join(entry.file_name());
+
+        if file_type.is_symlink() && options.copy_symlinks_as_links {
+            if destination_entry_path.exists() {
+                if options.skip_existing {
+                    continue;
+                }
+                if !options.overwrite {
+                    return Err(io::Error::new(io::ErrorKind::AlreadyExists,
+                        format!("Destination entry {} already exists.", destination_entry_path.display())));
+                }
+                remove_file(&destination_entry_path)?;
+            }
+            total_bytes_copied += copy_symlink(&entry_path, &destination_entry_path)?;
+            continue;
+        }
+
+        // A symlink pointing at a directory must still be recursed into when it is not being
+        // kept as a link, as entry.file_type() reports the link itself rather than its target.
+        let is_dir_entry = file_type.is_dir() || (file_type.is_symlink() && entry_path.is_dir());
+        if is_dir_entry {
+            total_bytes_copied += copy_dir(
+                entry_path.to_str().expect("Source subdirectory name has non valid unicode characters."),
+                destination_entry_path.to_str().expect("Destination subdirectory name has non valid unicode characters."),
+                options)?;
+        } else {
+            if destination_entry_path.exists() {
+                if options.skip_existing {
+                    continue;
+                }
+                if !options.overwrite {
+                    return Err(io::Error::new(io::ErrorKind::AlreadyExists,
+                        format!("Destination entry {} already exists.", destination_entry_path.display())));
+                }
+                remove_file(&destination_entry_path)?;
+            }
+            total_bytes_copied += copy(&entry_path, &destination_entry_path)?;
+        }
+    }
+    Ok(total_bytes_copied)
+}
+
+/// Recreate a symlink at destination pointing to the same target as source. Returns 0 as no
+/// content bytes are copied.
+#[cfg(unix)]
+fn copy_symlink(source: &Path, destination: &Path)-> Result<u64, io::Error> {
+    let target = read_link(source)?;
+    std::os::unix::fs::symlink(target, destination)?;
+    Ok(0)
+}
+
+/// Symlinks are not supported on this platform, so fall back to copying the pointed-to content.
+/// Returns the amount of copied bytes.
+#[cfg(not(unix))]
+fn copy_symlink(source: &Path, destination: &Path)-> Result<u64, io::Error> {
+    copy(source, destination)
+}
+
 
 #[cfg(test)]
 mod tests {
@@ -155,4 +298,166 @@ mod tests {
         assert!(temporal_file_name_path.exists());
         assert!(temporal_file_name_path2.exists());
     }
+
+    #[test]
+    fn test_atomic_write_creates_file_with_contents() {
+        let temp_folder: TempDir = tempdir()
+            .expect("Error creating temporal folder.");
+        let destination_path = temp_folder.path().join("atomic.txt");
+        match atomic_write(destination_path.to_str().expect("Error getting destination path."),
+                            b"atomic content") {
+            Ok(())=> assert!(true),
+            Err(_)=> assert!(false, "Atomic write failed.")
+        };
+        let contents = std::fs::read(&destination_path)
+            .expect("Error reading atomically written file.");
+        assert_eq!(b"atomic content".to_vec(), contents);
+    }
+
+    #[test]
+    fn test_atomic_write_creates_missing_parent_directory() {
+        let temp_folder: TempDir = tempdir()
+            .expect("Error creating temporal folder.");
+        let destination_path = temp_folder.path().join("missing/nested/atomic.txt");
+        match atomic_write(destination_path.to_str().expect("Error getting destination path."),
+                            b"nested content") {
+            Ok(())=> assert!(true),
+            Err(_)=> assert!(false, "Atomic write did not create missing parent directory.")
+        };
+        assert!(destination_path.exists());
+    }
+
+    #[test]
+    fn test_atomic_write_overwrites_existing_file() {
+        let temp_file = NamedTempFile::new()
+            .expect("Error creating temporal file for overwrite test.");
+        atomic_write(temp_file.path().to_str().expect("Error getting temporal file path."),
+                     b"original content")
+            .expect("Error performing first atomic write.");
+        atomic_write(temp_file.path().to_str().expect("Error getting temporal file path."),
+                     b"replacement content")
+            .expect("Error performing second atomic write.");
+        let contents = std::fs::read(temp_file.path())
+            .expect("Error reading overwritten file.");
+        assert_eq!(b"replacement content".to_vec(), contents);
+    }
+
+    #[test]
+    fn test_write_random_file() {
+        let temp_file = NamedTempFile::new()
+            .expect("Error creating temporal file for random write test.");
+        let desired_size: usize = 32;
+        write_random_file(temp_file.path().to_str().expect("Error getting temporal file path."),
+                           desired_size)
+            .expect("Error writing random file content.");
+        let contents = std::fs::read(temp_file.path())
+            .expect("Error reading randomly written file.");
+        assert_eq!(desired_size, contents.len(),
+                   "Randomly written file has not desired size of {} but {} instead",
+                   desired_size, contents.len());
+    }
+
+    #[test]
+    fn test_copy_dir_recreates_tree() {
+        let source_folder: TempDir = tempdir()
+            .expect("Error creating source temporal folder.");
+        let destination_folder: TempDir = tempdir()
+            .expect("Error creating destination temporal folder.");
+        std::fs::create_dir_all(source_folder.path().join("nested"))
+            .expect("Error creating nested source directory.");
+        std::fs::write(source_folder.path().join("top.txt"), b"top content")
+            .expect("Error writing top level source file.");
+        std::fs::write(source_folder.path().join("nested/inner.txt"), b"inner content")
+            .expect("Error writing nested source file.");
+        let destination = destination_folder.path().join("copy");
+        let total_bytes_copied = copy_dir(
+            source_folder.path().to_str().expect("Error getting source folder path."),
+            destination.to_str().expect("Error getting destination path."),
+            CopyOptions::default())
+            .expect("Error copying directory tree.");
+        assert_eq!(b"top content".len() as u64 + b"inner content".len() as u64, total_bytes_copied);
+        assert_eq!(b"top content".to_vec(),
+                   std::fs::read(destination.join("top.txt")).expect("Error reading copied file."));
+        assert_eq!(b"inner content".to_vec(),
+                   std::fs::read(destination.join("nested/inner.txt")).expect("Error reading copied nested file."));
+    }
+
+    #[test]
+    fn test_copy_dir_errors_on_existing_entry_without_overwrite() {
+        let source_folder: TempDir = tempdir()
+            .expect("Error creating source temporal folder.");
+        let destination_folder: TempDir = tempdir()
+            .expect("Error creating destination temporal folder.");
+        std::fs::write(source_folder.path().join("file.txt"), b"new content")
+            .expect("Error writing source file.");
+        std::fs::write(destination_folder.path().join("file.txt"), b"old content")
+            .expect("Error writing pre-existing destination file.");
+        match copy_dir(
+            source_folder.path().to_str().expect("Error getting source folder path."),
+            destination_folder.path().to_str().expect("Error getting destination folder path."),
+            CopyOptions::default()) {
+            Ok(_)=> assert!(false, "Copy should have failed on existing destination entry."),
+            Err(_)=> assert!(true)
+        };
+    }
+
+    #[test]
+    fn test_copy_dir_skip_existing_keeps_destination_content() {
+        let source_folder: TempDir = tempdir()
+            .expect("Error creating source temporal folder.");
+        let destination_folder: TempDir = tempdir()
+            .expect("Error creating destination temporal folder.");
+        std::fs::write(source_folder.path().join("file.txt"), b"new content")
+            .expect("Error writing source file.");
+        std::fs::write(destination_folder.path().join("file.txt"), b"old content")
+            .expect("Error writing pre-existing destination file.");
+        let options = CopyOptions{skip_existing: true, ..CopyOptions::default()};
+        copy_dir(
+            source_folder.path().to_str().expect("Error getting source folder path."),
+            destination_folder.path().to_str().expect("Error getting destination folder path."),
+            options)
+            .expect("Error copying directory tree with skip_existing.");
+        assert_eq!(b"old content".to_vec(),
+                   std::fs::read(destination_folder.path().join("file.txt")).expect("Error reading destination file."));
+    }
+
+    #[test]
+    fn test_copy_dir_ignore_missing_returns_zero() {
+        let destination_folder: TempDir = tempdir()
+            .expect("Error creating destination temporal folder.");
+        let options = CopyOptions{ignore_missing: true, ..CopyOptions::default()};
+        let total_bytes_copied = copy_dir(
+            "/tmp/this_source_does_not_exist_123456789",
+            destination_folder.path().to_str().expect("Error getting destination folder path."),
+            options)
+            .expect("Error copying directory tree with ignore_missing.");
+        assert_eq!(0, total_bytes_copied);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_copy_dir_recurses_into_symlinked_directory_by_default() {
+        let source_folder: TempDir = tempdir()
+            .expect("Error creating source temporal folder.");
+        let destination_folder: TempDir = tempdir()
+            .expect("Error creating destination temporal folder.");
+        std::fs::create_dir_all(source_folder.path().join("real_dir"))
+            .expect("Error creating real source directory.");
+        std::fs::write(source_folder.path().join("real_dir/inner.txt"), b"inner content")
+            .expect("Error writing file inside real source directory.");
+        std::os::unix::fs::symlink(source_folder.path().join("real_dir"),
+                                    source_folder.path().join("linked_dir"))
+            .expect("Error creating symlink to source directory.");
+        let destination = destination_folder.path().join("copy");
+        let total_bytes_copied = copy_dir(
+            source_folder.path().to_str().expect("Error getting source folder path."),
+            destination.to_str().expect("Error getting destination path."),
+            CopyOptions::default())
+            .expect("Error copying directory tree containing a symlinked directory.");
+        // real_dir and linked_dir are both copied, so inner.txt content is counted twice.
+        assert_eq!(2 * b"inner content".len() as u64, total_bytes_copied);
+        assert_eq!(b"inner content".to_vec(),
+                   std::fs::read(destination.join("linked_dir/inner.txt"))
+                       .expect("Error reading file copied through symlinked directory."));
+    }
 }
\ No newline at end of file
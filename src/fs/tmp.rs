@@ -1,8 +1,10 @@
 /// Module to manage temporal files and folders.
 
-use std::fs::remove_file;
-use std::path::Path;
-use tempfile::{NamedTempFile, tempdir, TempDir};
+use std::fs::{create_dir_all, rename, write};
+use std::path::{Path, PathBuf};
+use tempfile::{Builder, NamedTempFile, tempdir, TempDir};
+
+use crate::random::bytes::random_bytes;
 
 /// Context manager like struct to create temporal folder to perform tests inside.
 ///
@@ -36,6 +38,186 @@ impl TestEnvironment {
     pub fn path(&self)-> &Path{
         self.folder.as_ref()
     }
+
+    /// Create a file with given contents at relative_path inside this environment, creating any
+    /// missing intermediate directories.
+    ///
+    /// # Parameters:
+    /// * relative_path: Path relative to this environment root where file is to be created.
+    /// * contents: Bytes to write into the file.
+    ///
+    /// # Returns:
+    /// Absolute PathBuf to created file.
+    pub fn with_file<P, C>(&self, relative_path: P, contents: C)-> PathBuf
+        where P: AsRef<Path>, C: AsRef<[u8]> {
+        let file_path = self.path().join(relative_path);
+        if let Some(parent) = file_path.parent() {
+            create_dir_all(parent)
+                .expect("Error creating parent directories for sandbox file.");
+        }
+        write(&file_path, contents)
+            .expect("Error writing sandbox file content.");
+        file_path
+    }
+
+    /// Create an empty file at relative_path inside this environment, creating any missing
+    /// intermediate directories.
+    ///
+    /// # Parameters:
+    /// * relative_path: Path relative to this environment root where file is to be created.
+    ///
+    /// # Returns:
+    /// Absolute PathBuf to created file.
+    pub fn with_empty_file<P: AsRef<Path>>(&self, relative_path: P)-> PathBuf {
+        self.with_file(relative_path, b"")
+    }
+
+    /// Create a directory at relative_path inside this environment, creating any missing
+    /// intermediate directories.
+    ///
+    /// # Parameters:
+    /// * relative_path: Path relative to this environment root where directory is to be created.
+    ///
+    /// # Returns:
+    /// Absolute PathBuf to created directory.
+    pub fn mkdir<P: AsRef<Path>>(&self, relative_path: P)-> PathBuf {
+        let dir_path = self.path().join(relative_path);
+        create_dir_all(&dir_path)
+            .expect("Error creating sandbox directory.");
+        dir_path
+    }
+
+    /// Return a builder to customize naming and location of a new TestEnvironment.
+    pub fn builder()-> TestEnvironmentBuilder {
+        TestEnvironmentBuilder::new()
+    }
+
+    /// Consume this instance, disabling automatic cleanup, and return its path so it persists.
+    ///
+    /// # Returns:
+    /// Absolute PathBuf to the folder, which will not be removed when this instance would
+    /// otherwise have been dropped.
+    pub fn keep(self)-> PathBuf {
+        self.folder.keep()
+    }
+
+    /// Consume this instance, move it to destination_path and disable automatic cleanup.
+    ///
+    /// # Parameters:
+    /// * destination_path: Absolute path name where folder is to be moved.
+    ///
+    /// # Returns:
+    /// Absolute PathBuf to the folder at its new, permanent location.
+    pub fn persist<P: AsRef<Path>>(self, destination_path: P)-> PathBuf {
+        let source_path = self.folder.keep();
+        let destination_path = destination_path.as_ref().to_path_buf();
+        rename(&source_path, &destination_path)
+            .expect("Error persisting temporal test environment to destination path.");
+        destination_path
+    }
+}
+
+/// Builder to customize TestEnvironment creation: naming prefix/suffix, random suffix length
+/// and parent directory.
+///
+/// # Example
+/// ```rust
+/// use test_common::fs::tmp::TestEnvironment;
+///
+/// let test_folder = TestEnvironment::builder()
+///     .prefix("my_prefix_")
+///     .suffix("_my_suffix")
+///     .build();
+/// ```
+pub struct TestEnvironmentBuilder {
+    prefix: String,
+    suffix: String,
+    rand_bytes: usize,
+    parent_dir: Option<PathBuf>,
+}
+
+impl TestEnvironmentBuilder {
+    fn new()-> Self {
+        TestEnvironmentBuilder {
+            prefix: String::new(),
+            suffix: String::new(),
+            rand_bytes: 6,
+            parent_dir: None,
+        }
+    }
+
+    /// Set the prefix prepended to the generated folder name.
+    pub fn prefix<T: AsRef<str>>(mut self, prefix: T)-> Self {
+        self.prefix = prefix.as_ref().to_string();
+        self
+    }
+
+    /// Set the suffix appended to the generated folder name.
+    pub fn suffix<T: AsRef<str>>(mut self, suffix: T)-> Self {
+        self.suffix = suffix.as_ref().to_string();
+        self
+    }
+
+    /// Set how many random bytes are used to make the generated folder name unique.
+    pub fn rand_bytes(mut self, rand_bytes: usize)-> Self {
+        self.rand_bytes = rand_bytes;
+        self
+    }
+
+    /// Set the directory the generated folder is created inside, instead of the system default.
+    pub fn parent_dir<P: AsRef<Path>>(mut self, parent_dir: P)-> Self {
+        self.parent_dir = Some(parent_dir.as_ref().to_path_buf());
+        self
+    }
+
+    /// Build the TestEnvironment with the configured options.
+    #[must_use]
+    pub fn build(self)-> TestEnvironment {
+        let mut builder = Builder::new();
+        builder.prefix(&self.prefix).suffix(&self.suffix).rand_bytes(self.rand_bytes);
+        let temp_folder = match self.parent_dir {
+            Some(parent_dir)=> builder.tempdir_in(parent_dir),
+            None=> builder.tempdir(),
+        }.expect("Could not create a customized temporal test environment.");
+        TestEnvironment{folder: temp_folder}
+    }
+}
+
+/// Create an empty TestEnvironment, hand it to closure and tear it down once closure returns.
+///
+/// Populate the environment with TestEnvironment::with_file, TestEnvironment::with_empty_file
+/// and TestEnvironment::mkdir before running your test code against it inside closure.
+///
+/// # Parameters:
+/// * name: Label identifying this sandbox, used in the panic message if environment creation
+///   fails.
+/// * closure: Closure receiving a reference to the TestEnvironment, to populate and test against.
+///
+/// # Example
+/// ```rust
+/// use test_common::fs::tmp::setup;
+///
+/// setup("my_sandbox", |environment| {
+///     let file_path = environment.with_file("nested/fixture.txt", "content");
+///     // Do your operations against file_path.
+/// }); // Here sandbox is automatically removed.
+/// ```
+pub fn setup<F>(name: &str, closure: F)
+    where F: FnOnce(&TestEnvironment) {
+    setup_in(name, None::<&Path>, closure)
+}
+
+/// Implementation of setup() that lets tests force environment creation to fail by giving it a
+/// non existent parent_dir, without having to touch global state such as the TMPDIR env var.
+fn setup_in<F, P: AsRef<Path>>(name: &str, parent_dir: Option<P>, closure: F)
+    where F: FnOnce(&TestEnvironment) {
+    let mut builder = Builder::new();
+    let temp_folder = match parent_dir {
+        Some(parent_dir)=> builder.tempdir_in(parent_dir),
+        None=> builder.tempdir(),
+    }.unwrap_or_else(|error| panic!("Could not create temporal sandbox \"{}\": {}", name, error));
+    let environment = TestEnvironment{folder: temp_folder};
+    closure(&environment);
 }
 
 impl AsRef<Path> for TestEnvironment {
@@ -70,26 +252,129 @@ impl TestFile {
         TestFile{file: temp_file}
     }
 
+    /// Create a temporal file filled with size bytes of random binary content.
+    ///
+    /// # Parameters:
+    /// * size: Desired byte length for generated file content.
+    pub fn with_random_content(size: usize)-> Self {
+        let test_file = TestFile::new();
+        write(test_file.path(), random_bytes(size))
+            .expect("Error writing random content to temporal file.");
+        test_file
+    }
+
     /// Return a Path reference to a generated temporal file.
     pub fn path(&self)-> &Path {self.file.as_ref()}
-}
 
-impl Drop for TestFile{
-    /// Remove test file when it leaves scope.
-    fn drop(&mut self) {
-        remove_file(self.path()).expect("Error removing temporal file.");
+    /// Return a builder to customize naming and location of a new TestFile.
+    pub fn builder()-> TestFileBuilder {
+        TestFileBuilder::new()
+    }
+
+    /// Consume this instance, disabling automatic cleanup, and return its path so it persists.
+    ///
+    /// # Returns:
+    /// Absolute PathBuf to the file, which will not be removed when this instance would
+    /// otherwise have been dropped.
+    pub fn keep(self)-> PathBuf {
+        let TestFile{file} = self;
+        let (_file, path) = file.keep()
+            .expect("Error persisting temporal file.");
+        path
+    }
+
+    /// Consume this instance, move it to destination_path and disable automatic cleanup.
+    ///
+    /// # Parameters:
+    /// * destination_path: Absolute path name where file is to be moved.
+    ///
+    /// # Returns:
+    /// Absolute PathBuf to the file at its new, permanent location.
+    pub fn persist<P: AsRef<Path>>(self, destination_path: P)-> PathBuf {
+        let destination_path = destination_path.as_ref().to_path_buf();
+        let TestFile{file} = self;
+        file.persist(&destination_path)
+            .expect("Error persisting temporal file to destination path.");
+        destination_path
     }
 }
 
+// NamedTempFile automatically removes generated temp file, so implementing Drop trait is not
+// needed.
 impl AsRef<Path> for TestFile {
     fn as_ref(&self)-> &Path { self.path() }
 }
 
+/// Builder to customize TestFile creation: naming prefix/suffix, random suffix length and
+/// parent directory.
+///
+/// # Example
+/// ```rust
+/// use test_common::fs::tmp::TestFile;
+///
+/// let test_file = TestFile::builder()
+///     .prefix("my_prefix_")
+///     .suffix("_my_suffix")
+///     .build();
+/// ```
+pub struct TestFileBuilder {
+    prefix: String,
+    suffix: String,
+    rand_bytes: usize,
+    parent_dir: Option<PathBuf>,
+}
+
+impl TestFileBuilder {
+    fn new()-> Self {
+        TestFileBuilder {
+            prefix: String::new(),
+            suffix: String::new(),
+            rand_bytes: 6,
+            parent_dir: None,
+        }
+    }
+
+    /// Set the prefix prepended to the generated file name.
+    pub fn prefix<T: AsRef<str>>(mut self, prefix: T)-> Self {
+        self.prefix = prefix.as_ref().to_string();
+        self
+    }
+
+    /// Set the suffix appended to the generated file name.
+    pub fn suffix<T: AsRef<str>>(mut self, suffix: T)-> Self {
+        self.suffix = suffix.as_ref().to_string();
+        self
+    }
+
+    /// Set how many random bytes are used to make the generated file name unique.
+    pub fn rand_bytes(mut self, rand_bytes: usize)-> Self {
+        self.rand_bytes = rand_bytes;
+        self
+    }
+
+    /// Set the directory the generated file is created inside, instead of the system default.
+    pub fn parent_dir<P: AsRef<Path>>(mut self, parent_dir: P)-> Self {
+        self.parent_dir = Some(parent_dir.as_ref().to_path_buf());
+        self
+    }
+
+    /// Build the TestFile with the configured options.
+    pub fn build(self)-> TestFile {
+        let mut builder = Builder::new();
+        builder.prefix(&self.prefix).suffix(&self.suffix).rand_bytes(self.rand_bytes);
+        let temp_file = match self.parent_dir {
+            Some(parent_dir)=> builder.tempfile_in(parent_dir),
+            None=> builder.tempfile(),
+        }.expect("Could not create a customized temporal file.");
+        TestFile{file: temp_file}
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::env::temp_dir;
-    use std::fs::read_dir;
+    use std::fs::{read_dir, remove_file};
     use std::path::PathBuf;
 
     #[test]
@@ -128,6 +413,133 @@ mod tests {
         assert!(!test_folder_path.exists());
     }
 
+    #[test]
+    fn test_with_file_creates_nested_path() {
+        let test_folder = TestEnvironment::new();
+        let file_path = test_folder.with_file("nested/dir/fixture.txt", "hello");
+        assert!(file_path.exists(), "Sandbox file was not created.");
+        assert_eq!(test_folder.path().join("nested/dir/fixture.txt"), file_path);
+        let contents = std::fs::read_to_string(&file_path)
+            .expect("Error reading sandbox file contents.");
+        assert_eq!("hello", contents);
+    }
+
+    #[test]
+    fn test_with_empty_file_creates_empty_file() {
+        let test_folder = TestEnvironment::new();
+        let file_path = test_folder.with_empty_file("empty.txt");
+        assert!(file_path.exists(), "Sandbox file was not created.");
+        let metadata = file_path.metadata()
+            .expect("Error getting sandbox file metadata.");
+        assert_eq!(0, metadata.len(), "Sandbox file was not empty.");
+    }
+
+    #[test]
+    fn test_mkdir_creates_nested_directory() {
+        let test_folder = TestEnvironment::new();
+        let dir_path = test_folder.mkdir("nested/dir");
+        assert!(dir_path.is_dir(), "Sandbox directory was not created.");
+    }
+
+    #[test]
+    fn test_setup_populates_and_tears_down_environment() {
+        let mut sandbox_path = PathBuf::from("/");
+        setup("test_setup", |environment| {
+            sandbox_path = PathBuf::from(environment.path());
+            let file_path = environment.with_file("fixture.txt", "content");
+            assert!(file_path.exists(), "Sandbox file was not created.");
+        });
+        assert!(!sandbox_path.exists(), "Sandbox was not removed after setup.");
+    }
+
+    #[test]
+    fn test_setup_panic_message_includes_name_on_creation_failure() {
+        // Force environment creation to fail with a parent_dir that does not exist, instead of
+        // mutating the process-wide TMPDIR env var, which would race other parallel tests.
+        let result = std::panic::catch_unwind(|| {
+            setup_in("my_named_sandbox", Some("/this/path/does/not/exist"), |_environment| {});
+        });
+        let panic_payload = result.expect_err("setup() did not panic on sandbox creation failure.");
+        let panic_message = panic_payload.downcast_ref::<String>()
+            .map(String::as_str)
+            .or_else(|| panic_payload.downcast_ref::<&str>().copied())
+            .expect("Error reading panic message.");
+        assert!(panic_message.contains("my_named_sandbox"),
+                "Panic message \"{}\" did not include sandbox name.", panic_message);
+    }
+
+    #[test]
+    fn test_environment_builder_applies_prefix_and_suffix() {
+        let test_folder = TestEnvironment::builder()
+            .prefix("my_prefix_")
+            .suffix("_my_suffix")
+            .build();
+        let folder_name = test_folder.path().file_name()
+            .expect("Error getting test folder name.")
+            .to_str()
+            .expect("Test folder name has non valid unicode characters.");
+        assert!(folder_name.starts_with("my_prefix_"),
+                "Test folder name {} does not start with configured prefix.", folder_name);
+        assert!(folder_name.ends_with("_my_suffix"),
+                "Test folder name {} does not end with configured suffix.", folder_name);
+    }
+
+    #[test]
+    fn test_environment_persist_survives_drop() {
+        let test_folder = TestEnvironment::new();
+        let destination_parent = TestEnvironment::new();
+        let destination = destination_parent.path().join("persisted_environment");
+        let persisted_path = test_folder.persist(&destination);
+        assert_eq!(destination, persisted_path);
+        assert!(persisted_path.exists(), "Persisted environment does not exist.");
+    }
+
+    #[test]
+    fn test_file_builder_applies_prefix_and_suffix() {
+        let test_file = TestFile::builder()
+            .prefix("my_prefix_")
+            .suffix("_my_suffix")
+            .build();
+        let file_name = test_file.path().file_name()
+            .expect("Error getting test file name.")
+            .to_str()
+            .expect("Test file name has non valid unicode characters.");
+        assert!(file_name.starts_with("my_prefix_"),
+                "Test file name {} does not start with configured prefix.", file_name);
+        assert!(file_name.ends_with("_my_suffix"),
+                "Test file name {} does not end with configured suffix.", file_name);
+    }
+
+    #[test]
+    fn test_file_keep_survives_drop() {
+        let test_file = TestFile::new();
+        let file_path = test_file.keep();
+        assert!(file_path.exists(), "Kept file does not exist.");
+        remove_file(&file_path)
+            .expect("Error removing kept file during test cleanup.");
+    }
+
+    #[test]
+    fn test_file_persist_survives_drop() {
+        let test_file = TestFile::new();
+        let destination_parent = TestEnvironment::new();
+        let destination = destination_parent.path().join("persisted_file.txt");
+        let persisted_path = test_file.persist(&destination);
+        assert_eq!(destination, persisted_path);
+        assert!(persisted_path.exists(), "Persisted file does not exist.");
+    }
+
+    #[test]
+    fn test_with_random_content_has_desired_size() {
+        let desired_size: usize = 24;
+        let test_file = TestFile::with_random_content(desired_size);
+        let contents = std::fs::read(test_file.path())
+            .expect("Error reading randomly generated temporal file.");
+        assert_eq!(desired_size, contents.len(),
+                   "Temporal file with random content has not desired size of {} but {} instead",
+                   desired_size, contents.len());
+    }
+
     #[test]
     fn test_create_test_file() {
         let env_temp_dir = temp_dir();
@@ -0,0 +1,31 @@
+/// Random operations with raw bytes.
+
+use rand::prelude::*;
+
+/// Generate a vector of random bytes of desired length.
+///
+/// # Parameters:
+/// * len: Desired byte length for generated vector.
+///
+/// # Returns:
+/// * Generated vector of random bytes.
+pub fn random_bytes(len: usize)-> Vec<u8> {
+    let mut generated_bytes = vec![0u8; len];
+    rand::thread_rng().fill(&mut generated_bytes[..]);
+    generated_bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_random_bytes() {
+        let desired_length: usize = 16;
+        let generated_bytes = random_bytes(desired_length);
+        let generated_length = generated_bytes.len();
+        assert_eq!(desired_length, generated_length,
+                   "Generated random bytes vector has not desired length of {} but {} instead",
+                   desired_length, generated_length);
+    }
+}
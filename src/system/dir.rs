@@ -0,0 +1,82 @@
+/// Module to deal with current working directory.
+use std::env;
+use std::path::{Path, PathBuf};
+
+/// Context manager like struct to temporarily change current working directory to perform tests
+/// inside it.
+///
+/// Current working directory is stored at previous_dir before changing it to the given path.
+/// That previous_dir is restored as current working directory when this instance is dropped.
+///
+/// # Example
+/// ```rust
+/// use test_common::system::dir::TemporalWorkingDirectory;
+///
+/// {
+///     let _temp_dir = TemporalWorkingDirectory::new("/tmp");
+///     // Do your operations with new current directory.
+/// } // Here current directory is restored to former value.
+/// ```
+pub struct TemporalWorkingDirectory {
+    previous_dir: PathBuf,
+    current_dir: PathBuf,
+}
+
+impl TemporalWorkingDirectory {
+
+    /// Create a TemporalWorkingDirectory instance changing current directory to given path.
+    ///
+    /// Current working directory is stored at previous_dir attribute. That previous_dir is
+    /// restored as current working directory when this instance is dropped.
+    pub fn new<P: AsRef<Path>>(path: P) -> Self {
+        let previous_dir = env::current_dir()
+            .expect("Error getting current directory before changing it.");
+        env::set_current_dir(&path)
+            .expect("Error changing current directory.");
+        TemporalWorkingDirectory {
+            previous_dir,
+            current_dir: path.as_ref().to_path_buf(),
+        }
+    }
+
+    /// Return a Path reference to current working directory set by this guard.
+    pub fn current_dir(&self) -> &Path {
+        self.current_dir.as_path()
+    }
+
+    /// Return a Path reference to working directory previous to this guard creation.
+    pub fn previous_dir(&self) -> &Path {
+        self.previous_dir.as_path()
+    }
+}
+
+impl Drop for TemporalWorkingDirectory {
+
+    /// Leave current directory as it was before this guard was created.
+    fn drop(&mut self) {
+        env::set_current_dir(&self.previous_dir)
+            .expect("Error restoring previous current directory.");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fs::tmp::TestEnvironment;
+
+    #[test]
+    fn test_change_to_new_directory() {
+        let test_folder = TestEnvironment::new();
+        let previous_dir = env::current_dir()
+            .expect("Error getting current directory before test.");
+        {
+            let temp_dir = TemporalWorkingDirectory::new(test_folder.path());
+            assert_eq!(test_folder.path(), temp_dir.current_dir());
+            assert_eq!(previous_dir.as_path(), temp_dir.previous_dir());
+            assert_eq!(test_folder.path(), env::current_dir()
+                .expect("Error getting current directory inside test.").as_path());
+        } // temp_dir should restore previous current directory here as it is dropped.
+        assert_eq!(previous_dir, env::current_dir()
+            .expect("Error getting current directory after test."));
+    }
+}